@@ -1,8 +1,103 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::fs;
+use std::io::{self, BufRead};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone)]
+/// Parses a type from a single line of its on-disk record format.
+///
+/// Implementors pair with a matching serialization method (e.g. `to_record`)
+/// so that `T::new_from_string(&value.to_record())` round-trips.
+trait NewFromString: Sized {
+    fn new_from_string(s: &str) -> Result<Self, String>;
+}
+
+/// Relative field weights for ranked search, reflecting how much a match
+/// in that field should count towards a book's overall relevance.
+const TITLE_WEIGHT: f64 = 3.0;
+const AUTHOR_WEIGHT: f64 = 2.0;
+const ISBN_WEIGHT: f64 = 1.0;
+
+/// Flat score bonus added for an exact ISBN substring match, on top of
+/// `ISBN_WEIGHT`'s fuzzy per-token score, so that a match is always
+/// included in results even when every token's edit distance falls
+/// outside `token_match_score`'s budget.
+const ISBN_EXACT_MATCH_BONUS: f64 = 1.0;
+
+/// Returns the current time as Unix seconds, for callers that need a real
+/// clock rather than an injected `now`.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Computes the Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+
+    dp[a.len()][b.len()]
+}
+
+/// Scores how well a single query token matches a single candidate token,
+/// rejecting the match once edits exceed `max(1, query_token.len() / 4)`.
+/// Exact prefix matches always outscore typo matches.
+fn token_match_score(query_token: &str, candidate_token: &str) -> Option<f64> {
+    if candidate_token.starts_with(query_token) {
+        return Some(1.0);
+    }
+
+    let max_edits = std::cmp::max(1, query_token.chars().count() / 4);
+    let edits = levenshtein(query_token, candidate_token);
+    if edits <= max_edits {
+        Some(0.5 / (edits as f64 + 1.0))
+    } else {
+        None
+    }
+}
+
+/// Returns the best match score for `query_token` against any whitespace
+/// token in `field`, or `None` if no token matches within the edit budget.
+fn best_field_score(query_token: &str, field: &str) -> Option<f64> {
+    field
+        .to_lowercase()
+        .split_whitespace()
+        .filter_map(|token| token_match_score(query_token, token))
+        .fold(None, |best, score| match best {
+            Some(b) if b >= score => Some(b),
+            _ => Some(score),
+        })
+}
+
+/// Default replacement cost charged to a book when one isn't given
+/// explicitly, in cents.
+const DEFAULT_REPLACEMENT_COST_CENTS: u64 = 2000;
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Fine rate charged per day a book is overdue, in cents.
+const FINE_PER_DAY_CENTS: u64 = 25;
+
+#[derive(Debug, Clone, PartialEq)]
 struct Book {
     id: u32,
     title: String,
@@ -10,6 +105,7 @@ struct Book {
     isbn: String,
     available: bool,
     due_date: Option<u64>,
+    replacement_cost_cents: u64,
 }
 
 impl fmt::Display for Book {
@@ -29,28 +125,235 @@ impl fmt::Display for Book {
     }
 }
 
-#[derive(Debug)]
+impl Book {
+    /// Serializes this book as
+    /// `id:title:author:isbn:available:due_date:replacement_cost_cents`,
+    /// with an empty due date field when the book isn't checked out.
+    fn to_record(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.id,
+            self.title,
+            self.author,
+            self.isbn,
+            self.available,
+            self.due_date.map(|d| d.to_string()).unwrap_or_default(),
+            self.replacement_cost_cents
+        )
+    }
+}
+
+impl NewFromString for Book {
+    fn new_from_string(s: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = s.split(':').collect();
+        if fields.len() != 7 {
+            return Err(format!(
+                "expected 7 colon-delimited fields, got {}",
+                fields.len()
+            ));
+        }
+
+        let id = fields[0]
+            .parse::<u32>()
+            .map_err(|e| format!("invalid book id: {}", e))?;
+        let title = fields[1].to_string();
+        let author = fields[2].to_string();
+        let isbn = fields[3].to_string();
+        let available = fields[4]
+            .parse::<bool>()
+            .map_err(|e| format!("invalid available flag: {}", e))?;
+        let due_date = if fields[5].is_empty() {
+            None
+        } else {
+            Some(
+                fields[5]
+                    .parse::<u64>()
+                    .map_err(|e| format!("invalid due date: {}", e))?,
+            )
+        };
+        let replacement_cost_cents = fields[6]
+            .parse::<u64>()
+            .map_err(|e| format!("invalid replacement cost: {}", e))?;
+
+        Ok(Book {
+            id,
+            title,
+            author,
+            isbn,
+            available,
+            due_date,
+            replacement_cost_cents,
+        })
+    }
+}
+
+/// A member's kind of membership, which sets how many books they may have
+/// checked out at once (see `borrow_limit`). Members are not linked to any
+/// `Collection`: the kind is not tied to a home or secondary collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MembershipKind {
+    Primary,
+    Secondary,
+}
+
+impl MembershipKind {
+    /// Maximum number of books a member of this kind may have checked out
+    /// at once.
+    fn borrow_limit(&self) -> usize {
+        match self {
+            MembershipKind::Primary => 10,
+            MembershipKind::Secondary => 3,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MembershipKind::Primary => "Primary",
+            MembershipKind::Secondary => "Secondary",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 struct Member {
     id: u32,
     name: String,
     borrowed_books: Vec<u32>,
+    membership_kind: MembershipKind,
 }
 
 impl Member {
     fn new(id: u32, name: String) -> Self {
+        Member::with_membership_kind(id, name, MembershipKind::Primary)
+    }
+
+    fn with_membership_kind(id: u32, name: String, membership_kind: MembershipKind) -> Self {
         Member {
             id,
             name,
             borrowed_books: Vec::new(),
+            membership_kind,
+        }
+    }
+
+    /// Serializes this member as
+    /// `id:name:comma,joined,borrowed,ids:membership_kind`.
+    fn to_record(&self) -> String {
+        let borrowed = self
+            .borrowed_books
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{}:{}:{}:{}",
+            self.id,
+            self.name,
+            borrowed,
+            self.membership_kind.as_str()
+        )
+    }
+}
+
+impl NewFromString for Member {
+    fn new_from_string(s: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = s.split(':').collect();
+        if fields.len() != 4 {
+            return Err(format!(
+                "expected 4 colon-delimited fields, got {}",
+                fields.len()
+            ));
         }
+
+        let id = fields[0]
+            .parse::<u32>()
+            .map_err(|e| format!("invalid member id: {}", e))?;
+        let name = fields[1].to_string();
+        let borrowed_books = if fields[2].is_empty() {
+            Vec::new()
+        } else {
+            fields[2]
+                .split(',')
+                .map(|id| {
+                    id.parse::<u32>()
+                        .map_err(|e| format!("invalid borrowed book id: {}", e))
+                })
+                .collect::<Result<Vec<u32>, String>>()?
+        };
+        let membership_kind = match fields[3] {
+            "Primary" => MembershipKind::Primary,
+            "Secondary" => MembershipKind::Secondary,
+            other => return Err(format!("invalid membership kind: {}", other)),
+        };
+
+        Ok(Member {
+            id,
+            name,
+            borrowed_books,
+            membership_kind,
+        })
+    }
+}
+
+/// A named grouping of books, e.g. "Programming" or "Fiction".
+#[derive(Debug, PartialEq)]
+struct Collection {
+    id: u32,
+    name: String,
+    book_ids: Vec<u32>,
+}
+
+impl Collection {
+    /// Serializes this collection as
+    /// `id:name:comma,joined,book,ids`.
+    fn to_record(&self) -> String {
+        let book_ids = self
+            .book_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}:{}:{}", self.id, self.name, book_ids)
+    }
+}
+
+impl NewFromString for Collection {
+    fn new_from_string(s: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = s.split(':').collect();
+        if fields.len() != 3 {
+            return Err(format!(
+                "expected 3 colon-delimited fields, got {}",
+                fields.len()
+            ));
+        }
+
+        let id = fields[0]
+            .parse::<u32>()
+            .map_err(|e| format!("invalid collection id: {}", e))?;
+        let name = fields[1].to_string();
+        let book_ids = if fields[2].is_empty() {
+            Vec::new()
+        } else {
+            fields[2]
+                .split(',')
+                .map(|id| {
+                    id.parse::<u32>()
+                        .map_err(|e| format!("invalid book id: {}", e))
+                })
+                .collect::<Result<Vec<u32>, String>>()?
+        };
+
+        Ok(Collection { id, name, book_ids })
     }
 }
 
 struct Library {
     books: HashMap<u32, Book>,
     members: HashMap<u32, Member>,
+    collections: HashMap<u32, Collection>,
     next_book_id: u32,
     next_member_id: u32,
+    next_collection_id: u32,
 }
 
 impl Library {
@@ -58,12 +361,24 @@ impl Library {
         Library {
             books: HashMap::new(),
             members: HashMap::new(),
+            collections: HashMap::new(),
             next_book_id: 1,
             next_member_id: 1,
+            next_collection_id: 1,
         }
     }
 
     fn add_book(&mut self, title: String, author: String, isbn: String) -> u32 {
+        self.add_book_with_replacement_cost(title, author, isbn, DEFAULT_REPLACEMENT_COST_CENTS)
+    }
+
+    fn add_book_with_replacement_cost(
+        &mut self,
+        title: String,
+        author: String,
+        isbn: String,
+        replacement_cost_cents: u64,
+    ) -> u32 {
         let book = Book {
             id: self.next_book_id,
             title,
@@ -71,6 +386,7 @@ impl Library {
             isbn,
             available: true,
             due_date: None,
+            replacement_cost_cents,
         };
         self.books.insert(self.next_book_id, book);
         self.next_book_id += 1;
@@ -84,7 +400,67 @@ impl Library {
         self.next_member_id - 1
     }
 
-    fn check_out_book(&mut self, book_id: u32, member_id: u32) -> Result<(), String> {
+    fn add_member_with_kind(&mut self, name: String, kind: MembershipKind) -> u32 {
+        let member = Member::with_membership_kind(self.next_member_id, name, kind);
+        self.members.insert(self.next_member_id, member);
+        self.next_member_id += 1;
+        self.next_member_id - 1
+    }
+
+    fn add_collection(&mut self, name: String) -> u32 {
+        let collection = Collection {
+            id: self.next_collection_id,
+            name,
+            book_ids: Vec::new(),
+        };
+        self.collections.insert(self.next_collection_id, collection);
+        self.next_collection_id += 1;
+        self.next_collection_id - 1
+    }
+
+    fn assign_book_to_collection(
+        &mut self,
+        book_id: u32,
+        collection_id: u32,
+    ) -> Result<(), String> {
+        if !self.books.contains_key(&book_id) {
+            return Err("Book not found".to_string());
+        }
+        let collection = self
+            .collections
+            .get_mut(&collection_id)
+            .ok_or_else(|| "Collection not found".to_string())?;
+        if !collection.book_ids.contains(&book_id) {
+            collection.book_ids.push(book_id);
+        }
+        Ok(())
+    }
+
+    /// Lists every collection by name alongside the titles of the books
+    /// assigned to it, ordered by collection id.
+    fn get_all_collections(&self) -> Vec<(&str, Vec<&str>)> {
+        let mut collections: Vec<&Collection> = self.collections.values().collect();
+        collections.sort_by_key(|collection| collection.id);
+
+        collections
+            .into_iter()
+            .map(|collection| {
+                let titles = collection
+                    .book_ids
+                    .iter()
+                    .filter_map(|book_id| self.books.get(book_id))
+                    .map(|book| book.title.as_str())
+                    .collect();
+                (collection.name.as_str(), titles)
+            })
+            .collect()
+    }
+
+    /// Checks out `book_id` to `member_id`, setting its due date `now` plus
+    /// two weeks. `now` is Unix seconds and is taken as a parameter (rather
+    /// than read from `SystemTime::now()`) so callers, including tests, can
+    /// control the clock.
+    fn check_out_book(&mut self, book_id: u32, member_id: u32, now: u64) -> Result<(), String> {
         // Check if book and member exist
         if !self.books.contains_key(&book_id) {
             return Err("Book not found".to_string());
@@ -93,6 +469,17 @@ impl Library {
             return Err("Member not found".to_string());
         }
 
+        // Check the member hasn't hit their membership kind's borrowing limit
+        let member = self.members.get(&member_id).unwrap();
+        let limit = member.membership_kind.borrow_limit();
+        if member.borrowed_books.len() >= limit {
+            return Err(format!(
+                "{} members may not borrow more than {} books at a time",
+                member.membership_kind.as_str(),
+                limit
+            ));
+        }
+
         // Check if book is available
         let book = self.books.get_mut(&book_id).unwrap();
         if !book.available {
@@ -100,11 +487,7 @@ impl Library {
         }
 
         // Calculate due date (2 weeks from now)
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let two_weeks = 60 * 60 * 24 * 14;
+        let two_weeks = SECONDS_PER_DAY * 14;
 
         // Update book status
         book.available = false;
@@ -157,70 +540,533 @@ impl Library {
         }
     }
 
-    fn search_books(&self, query: &str) -> Vec<&Book> {
-        self.books
+    /// Ranked, typo-tolerant search over title, author and ISBN.
+    ///
+    /// The query is tokenized on whitespace and each token is matched
+    /// against title/author/ISBN tokens with bounded Levenshtein edit
+    /// distance, favoring prefix matches over typo matches and weighting
+    /// title matches above author matches above ISBN matches. Results are
+    /// sorted descending by total score, with exact ISBN substring matches
+    /// always ranked first and ties broken by book id.
+    fn search_books_ranked(&self, query: &str) -> Vec<(&Book, f64)> {
+        let query_tokens: Vec<String> = query
+            .to_lowercase()
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+        if query_tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let isbn_query = query.to_lowercase();
+        let mut scored: Vec<(&Book, f64, bool)> = self
+            .books
             .values()
-            .filter(|book| {
-                book.title.to_lowercase().contains(&query.to_lowercase())
-                    || book.author.to_lowercase().contains(&query.to_lowercase())
-                    || book.isbn.contains(query)
+            .filter_map(|book| {
+                let mut total = 0.0;
+                for token in &query_tokens {
+                    if let Some(score) = best_field_score(token, &book.title) {
+                        total += score * TITLE_WEIGHT;
+                    }
+                    if let Some(score) = best_field_score(token, &book.author) {
+                        total += score * AUTHOR_WEIGHT;
+                    }
+                    if let Some(score) = best_field_score(token, &book.isbn) {
+                        total += score * ISBN_WEIGHT;
+                    }
+                }
+
+                let isbn_exact_match = book.isbn.to_lowercase().contains(&isbn_query);
+                if isbn_exact_match {
+                    total += ISBN_EXACT_MATCH_BONUS;
+                }
+
+                if total > 0.0 {
+                    Some((book, total, isbn_exact_match))
+                } else {
+                    None
+                }
             })
+            .collect();
+
+        scored.sort_by(|a, b| {
+            b.2.cmp(&a.2)
+                .then_with(|| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.0.id.cmp(&b.0.id))
+        });
+
+        scored
+            .into_iter()
+            .map(|(book, score, _)| (book, score))
             .collect()
     }
+
+    /// Writes all books, members, and collections to `path` as line-based
+    /// records, one `[books]` section followed by one `[members]` section
+    /// followed by one `[collections]` section.
+    fn save_to_file(&self, path: &str) -> Result<(), String> {
+        let mut contents = String::new();
+
+        contents.push_str("[books]\n");
+        for book in self.books.values() {
+            contents.push_str(&book.to_record());
+            contents.push('\n');
+        }
+
+        contents.push_str("[members]\n");
+        for member in self.members.values() {
+            contents.push_str(&member.to_record());
+            contents.push('\n');
+        }
+
+        contents.push_str("[collections]\n");
+        for collection in self.collections.values() {
+            contents.push_str(&collection.to_record());
+            contents.push('\n');
+        }
+
+        fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path, e))
+    }
+
+    /// Rebuilds a `Library` from records previously written by
+    /// `save_to_file`, restoring `next_book_id`/`next_member_id`/
+    /// `next_collection_id` to one past the largest id seen.
+    fn load_from_file(path: &str) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+        let mut library = Library::new();
+        let mut section = "";
+
+        for line in contents.lines() {
+            if line == "[books]" || line == "[members]" || line == "[collections]" {
+                section = line;
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            match section {
+                "[books]" => {
+                    let book = Book::new_from_string(line)?;
+                    library.next_book_id = library.next_book_id.max(book.id + 1);
+                    library.books.insert(book.id, book);
+                }
+                "[members]" => {
+                    let member = Member::new_from_string(line)?;
+                    library.next_member_id = library.next_member_id.max(member.id + 1);
+                    library.members.insert(member.id, member);
+                }
+                "[collections]" => {
+                    let collection = Collection::new_from_string(line)?;
+                    library.next_collection_id =
+                        library.next_collection_id.max(collection.id + 1);
+                    library.collections.insert(collection.id, collection);
+                }
+                _ => return Err(format!("record outside of a section: {}", line)),
+            }
+        }
+
+        Ok(library)
+    }
+
+    /// Returns every checked-out book past its due date, alongside the
+    /// member it's checked out to and the number of days overdue,
+    /// computed from the stored Unix-seconds `due_date` against `now`.
+    fn overdue_books(&self, now: u64) -> Vec<(&Book, &Member, u64)> {
+        let mut overdue: Vec<(&Book, &Member, u64)> = self
+            .books
+            .values()
+            .filter_map(|book| {
+                let due_date = book.due_date?;
+                if now <= due_date {
+                    return None;
+                }
+                let days_overdue = (now - due_date) / SECONDS_PER_DAY;
+                let member = self
+                    .members
+                    .values()
+                    .find(|member| member.borrowed_books.contains(&book.id))?;
+                Some((book, member, days_overdue))
+            })
+            .collect();
+
+        overdue.sort_by_key(|(book, _, _)| book.id);
+        overdue
+    }
+
+    /// Totals the fine owed by `member_id` as of `now`, charging
+    /// `FINE_PER_DAY_CENTS` per day overdue per book, capped at each
+    /// book's own replacement cost.
+    fn fine_for_member(&self, member_id: u32, now: u64) -> Result<u64, String> {
+        if !self.members.contains_key(&member_id) {
+            return Err("Member not found".to_string());
+        }
+
+        let total = self
+            .overdue_books(now)
+            .into_iter()
+            .filter(|(_, member, _)| member.id == member_id)
+            .map(|(book, _, days_overdue)| {
+                (FINE_PER_DAY_CENTS * days_overdue).min(book.replacement_cost_cents)
+            })
+            .sum();
+
+        Ok(total)
+    }
 }
 
-fn main() {
-    let mut library = Library::new();
+/// Scans a command line into whitespace-separated tokens, treating a
+/// `"..."` run as a single token so titles and names containing spaces
+/// can be passed as one argument.
+struct Lexer {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Lexer {
+    fn new(line: &str) -> Self {
+        Lexer {
+            tokens: Self::tokenize(line),
+            pos: 0,
+        }
+    }
 
-    // Add some books
-    let book1_id = library.add_book(
-        "The Rust Programming Language".to_string(),
-        "Steve Klabnik".to_string(),
-        "978-1593278281".to_string(),
-    );
-    let book2_id = library.add_book(
-        "Zero To Production In Rust".to_string(),
-        "Luca Palmieri".to_string(),
-        "978-3001234567".to_string(),
-    );
-
-    // Add a member
-    let member_id = library.add_member("John Doe".to_string());
-
-    // Demonstrate book checkout
-    match library.check_out_book(book1_id, member_id) {
-        Ok(_) => println!("Book checked out successfully"),
-        Err(e) => println!("Error checking out book: {}", e),
-    }
-
-    // Search for books
-    println!("\nSearching for 'Rust' books:");
-    for book in library.search_books("Rust") {
-        println!("{}", book);
-    }
-
-    // Get member's borrowed books
-    match library.get_member_books(member_id) {
-        Ok(books) => {
-            println!("\nJohn Doe's borrowed books:");
-            for book in books {
-                println!("{}", book);
+    fn tokenize(line: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut chars = line.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+
+            if c == '"' {
+                chars.next();
+                let mut token = String::new();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                }
+                tokens.push(token);
+            } else {
+                let mut token = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push(token);
             }
         }
-        Err(e) => println!("Error getting member's books: {}", e),
+
+        tokens
     }
 
-    // Return the book
-    match library.return_book(book1_id, member_id) {
-        Ok(_) => println!("\nBook returned successfully"),
-        Err(e) => println!("Error returning book: {}", e),
+    /// Returns the token `lookahead` positions past the cursor without
+    /// consuming it.
+    fn peek(&self, lookahead: usize) -> Option<&str> {
+        self.tokens.get(self.pos + lookahead).map(|s| s.as_str())
     }
 }
 
+impl Iterator for Lexer {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+}
+
+/// A parsed REPL command, one variant per grammar rule:
+/// `COMMAND ::= add_book STR STR STR | add_member STR [STR] | checkout INT INT
+///            | return INT INT | search STR | list_member INT
+///            | save STR | load STR | add_collection STR
+///            | assign_collection INT INT | collections | overdue | fine INT`
+///
+/// `add_member`'s optional second STR is a membership kind, `primary`
+/// (the default) or `secondary`.
+enum Command {
+    AddBook {
+        title: String,
+        author: String,
+        isbn: String,
+    },
+    AddMember {
+        name: String,
+        kind: MembershipKind,
+    },
+    Checkout {
+        book_id: u32,
+        member_id: u32,
+    },
+    Return {
+        book_id: u32,
+        member_id: u32,
+    },
+    Search {
+        query: String,
+    },
+    ListMember {
+        member_id: u32,
+    },
+    Save {
+        path: String,
+    },
+    Load {
+        path: String,
+    },
+    AddCollection {
+        name: String,
+    },
+    AssignCollection {
+        book_id: u32,
+        collection_id: u32,
+    },
+    ListCollections,
+    Overdue,
+    Fine {
+        member_id: u32,
+    },
+}
+
+struct Parser;
+
+impl Parser {
+    fn parse(mut lexer: Lexer) -> Result<Command, String> {
+        if lexer.peek(0).is_none() {
+            return Err("expected a command".to_string());
+        }
+        let command = lexer.next().unwrap();
+
+        match command.as_str() {
+            "add_book" => Ok(Command::AddBook {
+                title: Self::expect_str(&mut lexer)?,
+                author: Self::expect_str(&mut lexer)?,
+                isbn: Self::expect_str(&mut lexer)?,
+            }),
+            "add_member" => {
+                let name = Self::expect_str(&mut lexer)?;
+                let kind = match lexer.next() {
+                    Some(token) => Self::parse_membership_kind(&token)?,
+                    None => MembershipKind::Primary,
+                };
+                Ok(Command::AddMember { name, kind })
+            }
+            "checkout" => Ok(Command::Checkout {
+                book_id: Self::expect_int(&mut lexer)?,
+                member_id: Self::expect_int(&mut lexer)?,
+            }),
+            "return" => Ok(Command::Return {
+                book_id: Self::expect_int(&mut lexer)?,
+                member_id: Self::expect_int(&mut lexer)?,
+            }),
+            "search" => Ok(Command::Search {
+                query: Self::expect_str(&mut lexer)?,
+            }),
+            "list_member" => Ok(Command::ListMember {
+                member_id: Self::expect_int(&mut lexer)?,
+            }),
+            "save" => Ok(Command::Save {
+                path: Self::expect_str(&mut lexer)?,
+            }),
+            "load" => Ok(Command::Load {
+                path: Self::expect_str(&mut lexer)?,
+            }),
+            "add_collection" => Ok(Command::AddCollection {
+                name: Self::expect_str(&mut lexer)?,
+            }),
+            "assign_collection" => Ok(Command::AssignCollection {
+                book_id: Self::expect_int(&mut lexer)?,
+                collection_id: Self::expect_int(&mut lexer)?,
+            }),
+            "collections" => Ok(Command::ListCollections),
+            "overdue" => Ok(Command::Overdue),
+            "fine" => Ok(Command::Fine {
+                member_id: Self::expect_int(&mut lexer)?,
+            }),
+            other => Err(format!("unknown command: {}", other)),
+        }
+    }
+
+    fn expect_str(lexer: &mut Lexer) -> Result<String, String> {
+        lexer
+            .next()
+            .ok_or_else(|| "expected a string argument".to_string())
+    }
+
+    fn expect_int(lexer: &mut Lexer) -> Result<u32, String> {
+        let token = lexer
+            .next()
+            .ok_or_else(|| "expected an integer argument".to_string())?;
+        token
+            .parse::<u32>()
+            .map_err(|e| format!("invalid integer '{}': {}", token, e))
+    }
+
+    fn parse_membership_kind(token: &str) -> Result<MembershipKind, String> {
+        match token.to_lowercase().as_str() {
+            "primary" => Ok(MembershipKind::Primary),
+            "secondary" => Ok(MembershipKind::Secondary),
+            other => Err(format!(
+                "invalid membership kind '{}': expected 'primary' or 'secondary'",
+                other
+            )),
+        }
+    }
+}
+
+/// Executes one parsed command against `library`, printing an `Ok`/`Err`
+/// line describing the result.
+fn execute_command(library: &mut Library, command: Command) {
+    match command {
+        Command::AddBook {
+            title,
+            author,
+            isbn,
+        } => {
+            let id = library.add_book(title, author, isbn);
+            println!("Ok: added book {}", id);
+        }
+        Command::AddMember { name, kind } => {
+            let id = match kind {
+                MembershipKind::Primary => library.add_member(name),
+                MembershipKind::Secondary => {
+                    library.add_member_with_kind(name, MembershipKind::Secondary)
+                }
+            };
+            println!("Ok: added member {}", id);
+        }
+        Command::Checkout {
+            book_id,
+            member_id,
+        } => match library.check_out_book(book_id, member_id, unix_now()) {
+            Ok(()) => println!("Ok: checked out book {} to member {}", book_id, member_id),
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::Return {
+            book_id,
+            member_id,
+        } => match library.return_book(book_id, member_id) {
+            Ok(()) => println!("Ok: returned book {} from member {}", book_id, member_id),
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::Search { query } => {
+            let results = library.search_books_ranked(&query);
+            if results.is_empty() {
+                println!("Ok: no matches");
+            }
+            for (book, score) in results {
+                println!("Ok: {} (score: {:.2})", book, score);
+            }
+        }
+        Command::ListMember { member_id } => match library.get_member_books(member_id) {
+            Ok(books) => {
+                if books.is_empty() {
+                    println!("Ok: no borrowed books");
+                }
+                for book in books {
+                    println!("Ok: {}", book);
+                }
+            }
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::Save { path } => match library.save_to_file(&path) {
+            Ok(()) => println!("Ok: saved to {}", path),
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::Load { path } => match Library::load_from_file(&path) {
+            Ok(loaded) => {
+                *library = loaded;
+                println!("Ok: loaded from {}", path);
+            }
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::AddCollection { name } => {
+            let id = library.add_collection(name);
+            println!("Ok: added collection {}", id);
+        }
+        Command::AssignCollection {
+            book_id,
+            collection_id,
+        } => match library.assign_book_to_collection(book_id, collection_id) {
+            Ok(()) => println!(
+                "Ok: assigned book {} to collection {}",
+                book_id, collection_id
+            ),
+            Err(e) => println!("Err: {}", e),
+        },
+        Command::ListCollections => {
+            let collections = library.get_all_collections();
+            if collections.is_empty() {
+                println!("Ok: no collections");
+            }
+            for (name, titles) in collections {
+                println!("Ok: {} -> {}", name, titles.join(", "));
+            }
+        }
+        Command::Overdue => {
+            let overdue = library.overdue_books(unix_now());
+            if overdue.is_empty() {
+                println!("Ok: no overdue books");
+            }
+            for (book, member, days_overdue) in overdue {
+                println!(
+                    "Ok: {} is {} day(s) overdue, borrowed by {}",
+                    book, days_overdue, member.name
+                );
+            }
+        }
+        Command::Fine { member_id } => match library.fine_for_member(member_id, unix_now()) {
+            Ok(cents) => println!("Ok: member {} owes {} cents", member_id, cents),
+            Err(e) => println!("Err: {}", e),
+        },
+    }
+}
+
+/// Reads commands from stdin, one per line, and drives the `Library` API
+/// until stdin is closed.
+fn run_repl(library: &mut Library) {
+    println!("Library REPL - enter commands, Ctrl+D to exit");
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                println!("Err: failed to read input: {}", e);
+                continue;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match Parser::parse(Lexer::new(&line)) {
+            Ok(command) => execute_command(library, command),
+            Err(e) => println!("Err: {}", e),
+        }
+    }
+}
+
+fn main() {
+    let mut library = Library::new();
+    run_repl(&mut library);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const TEST_NOW: u64 = 1_700_000_000;
+
     fn setup_library() -> (Library, u32, u32) {
         let mut library = Library::new();
         let book_id = library.add_book(
@@ -265,7 +1111,7 @@ mod tests {
     fn test_check_out_book_success() {
         let (mut library, book_id, member_id) = setup_library();
         
-        let result = library.check_out_book(book_id, member_id);
+        let result = library.check_out_book(book_id, member_id, TEST_NOW);
         assert!(result.is_ok());
         
         let book = library.books.get(&book_id).unwrap();
@@ -280,7 +1126,7 @@ mod tests {
     fn test_check_out_book_not_found() {
         let (mut library, _, member_id) = setup_library();
         
-        let result = library.check_out_book(999, member_id);
+        let result = library.check_out_book(999, member_id, TEST_NOW);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Book not found");
     }
@@ -290,10 +1136,10 @@ mod tests {
         let (mut library, book_id, member_id) = setup_library();
         
         // Check out the book first
-        library.check_out_book(book_id, member_id).unwrap();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
         
         // Try to check out the same book again
-        let result = library.check_out_book(book_id, member_id);
+        let result = library.check_out_book(book_id, member_id, TEST_NOW);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Book is not available");
     }
@@ -303,7 +1149,7 @@ mod tests {
         let (mut library, book_id, member_id) = setup_library();
         
         // Check out the book first
-        library.check_out_book(book_id, member_id).unwrap();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
         
         // Return the book
         let result = library.return_book(book_id, member_id);
@@ -327,28 +1173,6 @@ mod tests {
         assert_eq!(result.unwrap_err(), "This member has not borrowed this book");
     }
 
-    #[test]
-    fn test_search_books() {
-        let mut library = Library::new();
-        library.add_book(
-            "Rust Programming".to_string(),
-            "Author One".to_string(),
-            "111-1111111".to_string(),
-        );
-        library.add_book(
-            "Python Programming".to_string(),
-            "Author Two".to_string(),
-            "222-2222222".to_string(),
-        );
-        
-        let rust_books = library.search_books("Rust");
-        assert_eq!(rust_books.len(), 1);
-        assert_eq!(rust_books[0].title, "Rust Programming");
-        
-        let author_books = library.search_books("Author");
-        assert_eq!(author_books.len(), 2);
-    }
-
     #[test]
     fn test_get_member_books() {
         let (mut library, book_id, member_id) = setup_library();
@@ -359,7 +1183,7 @@ mod tests {
         assert_eq!(result.unwrap().len(), 0);
         
         // Check out a book
-        library.check_out_book(book_id, member_id).unwrap();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
         
         // Should now have one book
         let result = library.get_member_books(member_id);
@@ -375,4 +1199,528 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Member not found");
     }
+
+    #[test]
+    fn test_add_collection_and_assign_book() {
+        let mut library = Library::new();
+        let book_id = library.add_book(
+            "The Rust Programming Language".to_string(),
+            "Steve Klabnik".to_string(),
+            "978-1593278281".to_string(),
+        );
+        let collection_id = library.add_collection("Programming".to_string());
+
+        library
+            .assign_book_to_collection(book_id, collection_id)
+            .unwrap();
+
+        let collections = library.get_all_collections();
+        assert_eq!(
+            collections,
+            vec![("Programming", vec!["The Rust Programming Language"])]
+        );
+    }
+
+    #[test]
+    fn test_assign_book_to_collection_not_found() {
+        let mut library = Library::new();
+        let book_id = library.add_book(
+            "Test Book".to_string(),
+            "Test Author".to_string(),
+            "123-4567890".to_string(),
+        );
+
+        let result = library.assign_book_to_collection(book_id, 999);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Collection not found");
+    }
+
+    #[test]
+    fn test_secondary_member_borrow_limit() {
+        let mut library = Library::new();
+        let member_id = library
+            .add_member_with_kind("Test Member".to_string(), MembershipKind::Secondary);
+        let mut book_ids = Vec::new();
+        for i in 0..3 {
+            book_ids.push(library.add_book(
+                format!("Book {}", i),
+                "Some Author".to_string(),
+                format!("000-000000{}", i),
+            ));
+        }
+        let extra_book_id = library.add_book(
+            "One Too Many".to_string(),
+            "Some Author".to_string(),
+            "999-9999999".to_string(),
+        );
+
+        for book_id in &book_ids {
+            library.check_out_book(*book_id, member_id, TEST_NOW).unwrap();
+        }
+
+        let result = library.check_out_book(extra_book_id, member_id, TEST_NOW);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Secondary members may not borrow more than 3 books at a time"
+        );
+    }
+
+    #[test]
+    fn test_primary_member_borrow_limit_higher_than_secondary() {
+        let mut library = Library::new();
+        let member_id = library.add_member("Test Member".to_string());
+
+        for i in 0..10 {
+            let book_id = library.add_book(
+                format!("Book {}", i),
+                "Some Author".to_string(),
+                format!("000-000000{}", i),
+            );
+            library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_overdue_books_reports_days_late() {
+        let (mut library, book_id, member_id) = setup_library();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+
+        let due_date = library.books.get(&book_id).unwrap().due_date.unwrap();
+        let three_days_late = due_date + 3 * SECONDS_PER_DAY;
+
+        let overdue = library.overdue_books(three_days_late);
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].0.id, book_id);
+        assert_eq!(overdue[0].1.id, member_id);
+        assert_eq!(overdue[0].2, 3);
+    }
+
+    #[test]
+    fn test_overdue_books_empty_before_due_date() {
+        let (mut library, book_id, member_id) = setup_library();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+
+        assert!(library.overdue_books(TEST_NOW).is_empty());
+    }
+
+    #[test]
+    fn test_fine_for_member_accrues_per_day() {
+        let (mut library, book_id, member_id) = setup_library();
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+
+        let due_date = library.books.get(&book_id).unwrap().due_date.unwrap();
+        let four_days_late = due_date + 4 * SECONDS_PER_DAY;
+
+        let fine = library.fine_for_member(member_id, four_days_late).unwrap();
+        assert_eq!(fine, FINE_PER_DAY_CENTS * 4);
+    }
+
+    #[test]
+    fn test_fine_for_member_capped_at_replacement_cost() {
+        let mut library = Library::new();
+        let book_id = library.add_book_with_replacement_cost(
+            "Cheap Paperback".to_string(),
+            "Some Author".to_string(),
+            "000-0000000".to_string(),
+            50,
+        );
+        let member_id = library.add_member("Test Member".to_string());
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+
+        let due_date = library.books.get(&book_id).unwrap().due_date.unwrap();
+        let way_overdue = due_date + 1000 * SECONDS_PER_DAY;
+
+        let fine = library.fine_for_member(member_id, way_overdue).unwrap();
+        assert_eq!(fine, 50);
+    }
+
+    #[test]
+    fn test_fine_for_member_not_found() {
+        let library = Library::new();
+        let result = library.fine_for_member(999, TEST_NOW);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Member not found");
+    }
+
+    #[test]
+    fn test_search_books_ranked_typo_tolerant() {
+        let mut library = Library::new();
+        library.add_book(
+            "The Rust Programming Language".to_string(),
+            "Steve Klabnik".to_string(),
+            "978-1593278281".to_string(),
+        );
+
+        let results = library.search_books_ranked("Klabnick");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.author, "Steve Klabnik");
+    }
+
+    #[test]
+    fn test_search_books_ranked_prefers_prefix_over_typo() {
+        let mut library = Library::new();
+        let rust_id = library.add_book(
+            "Rust in Action".to_string(),
+            "Tim McNamara".to_string(),
+            "111-1111111".to_string(),
+        );
+        let rest_id = library.add_book(
+            "Rest APIs".to_string(),
+            "Some Author".to_string(),
+            "222-2222222".to_string(),
+        );
+
+        let results = library.search_books_ranked("Rust");
+        let rust_score = results.iter().find(|(b, _)| b.id == rust_id).unwrap().1;
+        let rest_score = results.iter().find(|(b, _)| b.id == rest_id).unwrap().1;
+        assert!(rust_score > rest_score);
+    }
+
+    #[test]
+    fn test_search_books_ranked_exact_isbn_first() {
+        let mut library = Library::new();
+        library.add_book(
+            "Rust in Action".to_string(),
+            "Tim McNamara".to_string(),
+            "111-1111111".to_string(),
+        );
+        let isbn_book_id = library.add_book(
+            "A Completely Unrelated Title".to_string(),
+            "Nobody Relevant".to_string(),
+            "rust-isbn-42".to_string(),
+        );
+
+        let results = library.search_books_ranked("rust");
+        assert_eq!(results[0].0.id, isbn_book_id);
+    }
+
+    #[test]
+    fn test_search_books_ranked_isbn_midstring_substring_always_included() {
+        let mut library = Library::new();
+        let isbn_book_id = library.add_book(
+            "The Rust Programming Language".to_string(),
+            "Steve Klabnik".to_string(),
+            "978-1593278281".to_string(),
+        );
+
+        // "159327" sits in the middle of the ISBN: `contains` is true, but
+        // it's neither a prefix of the ISBN nor within the fuzzy edit
+        // budget against the whole ISBN token, so the book must be kept
+        // in results (and ranked first) purely on the exact-match bonus.
+        let results = library.search_books_ranked("159327");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.id, isbn_book_id);
+    }
+
+    #[test]
+    fn test_search_books_ranked_no_match() {
+        let mut library = Library::new();
+        library.add_book(
+            "The Rust Programming Language".to_string(),
+            "Steve Klabnik".to_string(),
+            "978-1593278281".to_string(),
+        );
+
+        assert!(library.search_books_ranked("xyzzyqwerty").is_empty());
+    }
+
+    #[test]
+    fn test_search_books_ranked_typo_tolerant_isbn() {
+        let mut library = Library::new();
+        library.add_book(
+            "The Rust Programming Language".to_string(),
+            "Steve Klabnik".to_string(),
+            "978-1593278281".to_string(),
+        );
+
+        // Missing one digit from the real ISBN.
+        let results = library.search_books_ranked("978-159327821");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.isbn, "978-1593278281");
+    }
+
+    #[test]
+    fn test_book_record_round_trip() {
+        let book = Book {
+            id: 1,
+            title: "The Rust Programming Language".to_string(),
+            author: "Steve Klabnik".to_string(),
+            isbn: "978-1593278281".to_string(),
+            available: false,
+            due_date: Some(1699999999),
+            replacement_cost_cents: 3500,
+        };
+
+        let record = book.to_record();
+        let parsed = Book::new_from_string(&record).unwrap();
+        assert_eq!(parsed, book);
+    }
+
+    #[test]
+    fn test_book_record_round_trip_no_due_date() {
+        let book = Book {
+            id: 2,
+            title: "Zero To Production In Rust".to_string(),
+            author: "Luca Palmieri".to_string(),
+            isbn: "978-3001234567".to_string(),
+            available: true,
+            due_date: None,
+            replacement_cost_cents: DEFAULT_REPLACEMENT_COST_CENTS,
+        };
+
+        let parsed = Book::new_from_string(&book.to_record()).unwrap();
+        assert_eq!(parsed, book);
+    }
+
+    #[test]
+    fn test_book_record_wrong_field_count() {
+        let result = Book::new_from_string("1:Title:Author:ISBN");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_member_record_round_trip() {
+        let mut member = Member::new(1, "John Doe".to_string());
+        member.borrowed_books = vec![3, 7, 12];
+
+        let parsed = Member::new_from_string(&member.to_record()).unwrap();
+        assert_eq!(parsed, member);
+    }
+
+    #[test]
+    fn test_member_record_round_trip_no_borrowed_books() {
+        let member = Member::new(2, "Jane Doe".to_string());
+
+        let parsed = Member::new_from_string(&member.to_record()).unwrap();
+        assert_eq!(parsed, member);
+    }
+
+    #[test]
+    fn test_save_and_load_library_round_trip() {
+        let mut library = Library::new();
+        let book_id = library.add_book(
+            "Test Book".to_string(),
+            "Test Author".to_string(),
+            "123-4567890".to_string(),
+        );
+        let member_id = library.add_member("Test Member".to_string());
+        library.check_out_book(book_id, member_id, TEST_NOW).unwrap();
+        let collection_id = library.add_collection("Sci-Fi".to_string());
+        library
+            .assign_book_to_collection(book_id, collection_id)
+            .unwrap();
+
+        let path = std::env::temp_dir().join("library_system_test_save_and_load.txt");
+        let path_str = path.to_str().unwrap();
+
+        library.save_to_file(path_str).unwrap();
+        let loaded = Library::load_from_file(path_str).unwrap();
+        fs::remove_file(path_str).unwrap();
+
+        assert_eq!(loaded.books.get(&book_id), library.books.get(&book_id));
+        assert_eq!(
+            loaded.members.get(&member_id),
+            library.members.get(&member_id)
+        );
+        assert_eq!(
+            loaded.collections.get(&collection_id),
+            library.collections.get(&collection_id)
+        );
+        assert_eq!(loaded.next_book_id, library.next_book_id);
+        assert_eq!(loaded.next_member_id, library.next_member_id);
+        assert_eq!(loaded.next_collection_id, library.next_collection_id);
+    }
+
+    #[test]
+    fn test_lexer_splits_on_whitespace() {
+        let tokens: Vec<String> = Lexer::new("checkout 1 2").collect();
+        assert_eq!(tokens, vec!["checkout", "1", "2"]);
+    }
+
+    #[test]
+    fn test_lexer_keeps_quoted_literal_as_one_token() {
+        let tokens: Vec<String> =
+            Lexer::new("add_book \"The Rust Programming Language\" \"Steve Klabnik\" isbn-1")
+                .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                "add_book",
+                "The Rust Programming Language",
+                "Steve Klabnik",
+                "isbn-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_peek_does_not_consume() {
+        let mut lexer = Lexer::new("search Rust");
+        assert_eq!(lexer.peek(0), Some("search"));
+        assert_eq!(lexer.peek(1), Some("Rust"));
+        assert_eq!(lexer.peek(2), None);
+        assert_eq!(lexer.next(), Some("search".to_string()));
+        assert_eq!(lexer.peek(0), Some("Rust"));
+    }
+
+    #[test]
+    fn test_parser_add_book() {
+        let command = Parser::parse(Lexer::new(
+            "add_book \"Rust in Action\" \"Tim McNamara\" 111-1111111",
+        ))
+        .unwrap();
+        match command {
+            Command::AddBook {
+                title,
+                author,
+                isbn,
+            } => {
+                assert_eq!(title, "Rust in Action");
+                assert_eq!(author, "Tim McNamara");
+                assert_eq!(isbn, "111-1111111");
+            }
+            _ => panic!("expected an AddBook command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_checkout() {
+        let command = Parser::parse(Lexer::new("checkout 1 2")).unwrap();
+        match command {
+            Command::Checkout {
+                book_id,
+                member_id,
+            } => {
+                assert_eq!(book_id, 1);
+                assert_eq!(member_id, 2);
+            }
+            _ => panic!("expected a Checkout command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_save_and_load() {
+        match Parser::parse(Lexer::new("save library.txt")).unwrap() {
+            Command::Save { path } => assert_eq!(path, "library.txt"),
+            _ => panic!("expected a Save command"),
+        }
+
+        match Parser::parse(Lexer::new("load library.txt")).unwrap() {
+            Command::Load { path } => assert_eq!(path, "library.txt"),
+            _ => panic!("expected a Load command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_add_member_defaults_to_primary() {
+        match Parser::parse(Lexer::new("add_member \"Jane Doe\"")).unwrap() {
+            Command::AddMember { name, kind } => {
+                assert_eq!(name, "Jane Doe");
+                assert_eq!(kind, MembershipKind::Primary);
+            }
+            _ => panic!("expected an AddMember command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_add_member_accepts_secondary_kind() {
+        match Parser::parse(Lexer::new("add_member \"Jane Doe\" secondary")).unwrap() {
+            Command::AddMember { name, kind } => {
+                assert_eq!(name, "Jane Doe");
+                assert_eq!(kind, MembershipKind::Secondary);
+            }
+            _ => panic!("expected an AddMember command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_rejects_invalid_membership_kind() {
+        let result = Parser::parse(Lexer::new("add_member \"Jane Doe\" guest"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_add_collection_and_assign_collection() {
+        match Parser::parse(Lexer::new("add_collection \"Sci-Fi\"")).unwrap() {
+            Command::AddCollection { name } => assert_eq!(name, "Sci-Fi"),
+            _ => panic!("expected an AddCollection command"),
+        }
+
+        match Parser::parse(Lexer::new("assign_collection 1 2")).unwrap() {
+            Command::AssignCollection {
+                book_id,
+                collection_id,
+            } => {
+                assert_eq!(book_id, 1);
+                assert_eq!(collection_id, 2);
+            }
+            _ => panic!("expected an AssignCollection command"),
+        }
+    }
+
+    #[test]
+    fn test_parser_collections_overdue_and_fine() {
+        match Parser::parse(Lexer::new("collections")).unwrap() {
+            Command::ListCollections => {}
+            _ => panic!("expected a ListCollections command"),
+        }
+        match Parser::parse(Lexer::new("overdue")).unwrap() {
+            Command::Overdue => {}
+            _ => panic!("expected an Overdue command"),
+        }
+        match Parser::parse(Lexer::new("fine 1")).unwrap() {
+            Command::Fine { member_id } => assert_eq!(member_id, 1),
+            _ => panic!("expected a Fine command"),
+        }
+    }
+
+    #[test]
+    fn test_execute_command_save_and_load_round_trip() {
+        let mut library = Library::new();
+        let book_id = library.add_book(
+            "Test Book".to_string(),
+            "Test Author".to_string(),
+            "123-4567890".to_string(),
+        );
+        let path = std::env::temp_dir().join("library_system_test_execute_save_load.txt");
+        let path_str = path.to_str().unwrap().to_string();
+
+        execute_command(
+            &mut library,
+            Command::Save {
+                path: path_str.clone(),
+            },
+        );
+
+        let mut loaded = Library::new();
+        execute_command(&mut loaded, Command::Load { path: path_str });
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.books.get(&book_id), library.books.get(&book_id));
+    }
+
+    #[test]
+    fn test_parser_rejects_unknown_command() {
+        let result = Parser::parse(Lexer::new("frobnicate 1 2"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_rejects_missing_argument() {
+        let result = Parser::parse(Lexer::new("checkout 1"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_rejects_non_integer_argument() {
+        let result = Parser::parse(Lexer::new("checkout one two"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_rejects_empty_line() {
+        let result = Parser::parse(Lexer::new(""));
+        assert!(result.is_err());
+    }
 }
\ No newline at end of file